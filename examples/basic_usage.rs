@@ -1,61 +1,52 @@
-use sljf_datastructure::MerklePatriciaTree;
+use sljf_datastructure::PrefixTree;
 
 fn main() {
-    println!("Merkle Patricia Tree Demo");
-    println!("========================");
+    println!("Prefix Tree Demo");
+    println!("================");
 
     // Create a new tree
-    let mut tree = MerklePatriciaTree::new();
+    let mut tree = PrefixTree::new();
 
     // Insert the keys from the diagram
     println!("\n1. Inserting key-value pairs...");
-    tree.insert(b"foo", b"Value at 'foo'".to_vec()).unwrap();
-    tree.insert(b"foobar", b"Value at 'foobar'".to_vec())
-        .unwrap();
-    tree.insert(b"foofoo", b"Value at 'foofoo'".to_vec())
-        .unwrap();
-    tree.insert(b"bar", b"Value at 'bar'".to_vec()).unwrap();
+    tree.insert("foo", "Value at 'foo'".to_string());
+    tree.insert("foobar", "Value at 'foobar'".to_string());
+    tree.insert("foofoo", "Value at 'foofoo'".to_string());
+    tree.insert("bar", "Value at 'bar'".to_string());
 
     println!("   ✓ Inserted 4 key-value pairs");
     println!("   ✓ Tree size: {}", tree.len());
 
-    // Display the root hash
-    if let Some(root_hash) = tree.root_hash() {
-        println!("   ✓ Root hash: {}", hex::encode(root_hash));
-    }
+    // Display the root commitment
+    println!("   ✓ Root hash: {}", hex_encode(&tree.root_hash()));
 
     // Retrieve values
     println!("\n2. Retrieving values...");
-    let keys = [&b"foo"[..], &b"foobar"[..], &b"foofoo"[..], &b"bar"[..]];
-    for key in &keys {
+    for key in ["foo", "foobar", "foofoo", "bar"] {
         if let Some(value) = tree.get(key) {
-            println!(
-                "   {} -> {}",
-                String::from_utf8_lossy(key),
-                String::from_utf8_lossy(&value)
-            );
+            println!("   {} -> {}", key, value);
         }
     }
 
     // Test non-existent key
     println!("\n3. Testing non-existent key...");
-    if tree.get(b"baz").is_none() {
+    if tree.get("baz").is_none() {
         println!("   ✓ 'baz' not found (as expected)");
     }
 
-    // Verify tree integrity
-    println!("\n4. Verifying tree integrity...");
-    if tree.verify_integrity() {
-        println!("   ✓ Tree integrity verified");
-    } else {
-        println!("   ✗ Tree integrity check failed");
-    }
+    // Verify a Merkle inclusion proof
+    println!("\n4. Verifying a Merkle inclusion proof...");
+    let root = tree.root_hash();
+    let proof = tree.prove("foobar").expect("key should be present");
+    let ok = sljf_datastructure::verify_proof(root, "foobar", b"Value at 'foobar'", &proof);
+    println!("   ✓ Proof for 'foobar' verifies: {}", ok);
 
     // List all keys
     println!("\n5. All keys in the tree:");
-    let all_keys = tree.keys();
+    let mut all_keys = tree.keys();
+    all_keys.sort();
     for key in &all_keys {
-        println!("   - {}", String::from_utf8_lossy(key));
+        println!("   - {}", key);
     }
 
     // Display the tree structure
@@ -70,14 +61,17 @@ fn main() {
 
     // Update an existing key
     println!("\n8. Updating existing key...");
-    let old_value = tree.get(b"foo").unwrap();
-    tree.insert(b"foo", b"Updated value for 'foo'".to_vec())
-        .unwrap();
-    let new_value = tree.get(b"foo").unwrap();
+    let old_value = tree.insert("foo", "Updated value for 'foo'".to_string());
+    let new_value = tree.get("foo").unwrap();
 
-    println!("   Old value: {}", String::from_utf8_lossy(&old_value));
-    println!("   New value: {}", String::from_utf8_lossy(&new_value));
+    println!("   Old value: {}", old_value.unwrap());
+    println!("   New value: {}", new_value);
     println!("   Tree size remains: {} (no new key added)", tree.len());
 
-    println!("\nDemo completed successfully! 🎉");
+    println!("\nDemo completed successfully!");
+}
+
+/// Minimal hex encoding so this example doesn't need an extra dependency.
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
 }
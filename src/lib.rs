@@ -5,7 +5,9 @@
 
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::fmt;
 
 /// A hash type used for node identification and integrity verification
 pub type Hash = [u8; 32];
@@ -20,36 +22,243 @@ pub fn hash_bytes(data: &[u8]) -> Hash {
     hash
 }
 
-/// Node types in the Prefix Tree.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum Node {
+/// Tag byte prefixed to a leaf's contents before hashing, so a leaf can
+/// never collide with a branch node that happens to serialize to the same
+/// bytes.
+const LEAF_TAG: u8 = 0x00;
+
+/// Tag byte prefixed to a branch node's encoded children before hashing.
+const NODE_TAG: u8 = 0x01;
+
+/// Node types in the Prefix Tree, generic over the stored value type `V`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "V: Serialize",
+    deserialize = "V: serde::de::DeserializeOwned"
+))]
+pub enum Node<V> {
     /// Leaf node containing a value. The key for this leaf is the edge in the parent's map.
-    Leaf { value: String },
+    Leaf {
+        value: V,
+        /// Cached Keccak256 commitment for this leaf, cleared whenever the
+        /// value changes so it gets recomputed on next use.
+        #[serde(skip)]
+        hash_cache: Cell<Option<Hash>>,
+    },
     /// A branch node that has children. It can also have a value, stored in a special ":" child.
     Node {
-        children: HashMap<String, Box<Node>>,
+        children: HashMap<String, Box<Node<V>>>,
+        /// Cached Keccak256 commitment for this subtree, cleared whenever a
+        /// descendant is modified so it gets recomputed on next use.
+        #[serde(skip)]
+        hash_cache: Cell<Option<Hash>>,
     },
 }
 
-/// The main Prefix Tree structure.
+impl<V: PartialEq> PartialEq for Node<V> {
+    // The hash cache is a derived, interior-mutable memo, not part of the
+    // node's identity, so equality ignores it.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Node::Leaf { value: a, .. }, Node::Leaf { value: b, .. }) => a == b,
+            (Node::Node { children: a, .. }, Node::Node { children: b, .. }) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<V> Node<V> {
+    fn leaf(value: V) -> Self {
+        Node::Leaf {
+            value,
+            hash_cache: Cell::new(None),
+        }
+    }
+
+    fn branch(children: HashMap<String, Box<Node<V>>>) -> Self {
+        Node::Node {
+            children,
+            hash_cache: Cell::new(None),
+        }
+    }
+
+    /// Drop this node's cached hash. Called on every node along the path to
+    /// a modification, since its contribution to the hash has changed.
+    fn invalidate_cache(&self) {
+        match self {
+            Node::Leaf { hash_cache, .. } | Node::Node { hash_cache, .. } => {
+                hash_cache.set(None)
+            }
+        }
+    }
+}
+
+impl<V: AsRef<[u8]>> Node<V> {
+    /// Compute (or return the cached) Keccak256 commitment for this node.
+    ///
+    /// A leaf hashes to `keccak256(0x00 || value)`. A branch hashes to
+    /// `keccak256(0x01 || for each child in lexicographically-sorted edge
+    /// order: len(edge) || edge || child_hash)`, so two subtrees with
+    /// identical contents always hash identically regardless of `HashMap`
+    /// iteration order.
+    fn commitment(&self) -> Hash {
+        match self {
+            Node::Leaf { value, hash_cache } => {
+                if let Some(hash) = hash_cache.get() {
+                    return hash;
+                }
+                let value = value.as_ref();
+                let mut buf = Vec::with_capacity(1 + value.len());
+                buf.push(LEAF_TAG);
+                buf.extend_from_slice(value);
+                let hash = hash_bytes(&buf);
+                hash_cache.set(Some(hash));
+                hash
+            }
+            Node::Node {
+                children,
+                hash_cache,
+            } => {
+                if let Some(hash) = hash_cache.get() {
+                    return hash;
+                }
+                let mut entries: Vec<_> = children.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+
+                let mut buf = vec![NODE_TAG];
+                for (edge, child) in entries {
+                    encode_edge(&mut buf, edge, child.commitment());
+                }
+                let hash = hash_bytes(&buf);
+                hash_cache.set(Some(hash));
+                hash
+            }
+        }
+    }
+}
+
+/// Append `len(edge) || edge || child_hash` to `buf`, the canonical
+/// per-child encoding used both when hashing a branch and when hashing the
+/// reconstructed branches of a [`Proof`].
+fn encode_edge(buf: &mut Vec<u8>, edge: &str, child_hash: Hash) {
+    buf.extend_from_slice(&(edge.len() as u32).to_be_bytes());
+    buf.extend_from_slice(edge.as_bytes());
+    buf.extend_from_slice(&child_hash);
+}
+
+/// One level of a [`Proof`]: the hashes of every sibling of the node on the
+/// proof path, plus the edge that continues along the path. The path
+/// child's own hash is never stored here — the verifier recomputes it from
+/// the next (deeper) step and splices it back in.
+#[derive(Debug, Clone, PartialEq)]
+struct ProofStep {
+    /// `(edge, hash)` for every child at this level except `path_edge`.
+    siblings: Vec<(String, Hash)>,
+    /// The edge of the child that continues toward the proven key.
+    path_edge: String,
+}
+
+/// A Merkle inclusion proof for a single key, returned by
+/// [`PrefixTree::prove`] and checked by [`verify_proof`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Proof {
+    /// Proof steps ordered from the root down to the leaf's parent.
+    steps: Vec<ProofStep>,
+}
+
+/// An error produced while decoding a [`PrefixTree`] from the binary
+/// format written by [`PrefixTree::to_bytes`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeError {
+    /// The byte stream ended before a complete tree could be decoded.
+    UnexpectedEof,
+    /// A node tag byte was neither [`LEAF_TAG`] nor [`NODE_TAG`], or the
+    /// root node was a leaf (reported as `InvalidTag(LEAF_TAG)`).
+    InvalidTag(u8),
+    /// An edge string was not valid UTF-8.
+    InvalidEdge,
+    /// A leaf's value bytes could not be converted back into `V`.
+    InvalidValue(String),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEof => write!(f, "unexpected end of input"),
+            DecodeError::InvalidTag(tag) => write!(f, "invalid node tag byte: {:#04x}", tag),
+            DecodeError::InvalidEdge => write!(f, "edge bytes were not valid UTF-8"),
+            DecodeError::InvalidValue(msg) => write!(f, "invalid leaf value: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Append a varint-length-prefixed byte string to `buf`.
+fn encode_len_prefixed(buf: &mut Vec<u8>, bytes: &[u8]) {
+    encode_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+/// Append `value` to `buf` as an unsigned LEB128 varint.
+fn encode_varint(buf: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf.push(byte);
+            return;
+        }
+        buf.push(byte | 0x80);
+    }
+}
+
+/// Read an unsigned LEB128 varint from `bytes` starting at `*pos`, advancing `*pos` past it.
+fn decode_varint(bytes: &[u8], pos: &mut usize) -> Result<u64, DecodeError> {
+    let mut result: u64 = 0;
+    let mut shift = 0;
+    loop {
+        let byte = *bytes.get(*pos).ok_or(DecodeError::UnexpectedEof)?;
+        *pos += 1;
+        result |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(result);
+        }
+        shift += 7;
+    }
+}
+
+/// Read a varint-length-prefixed byte slice from `bytes` starting at `*pos`, advancing `*pos`
+/// past it.
+fn decode_len_prefixed<'a>(bytes: &'a [u8], pos: &mut usize) -> Result<&'a [u8], DecodeError> {
+    let len = decode_varint(bytes, pos)? as usize;
+    let end = pos.checked_add(len).ok_or(DecodeError::UnexpectedEof)?;
+    let slice = bytes.get(*pos..end).ok_or(DecodeError::UnexpectedEof)?;
+    *pos = end;
+    Ok(slice)
+}
+
+/// The main Prefix Tree structure, generic over the stored value type `V`.
 #[derive(Debug, Clone)]
-pub struct PrefixTree {
-    root: Box<Node>,
+pub struct PrefixTree<V> {
+    root: Box<Node<V>>,
+    /// Number of keys currently stored, maintained incrementally by
+    /// [`Self::insert`]/[`Self::remove`] so [`Self::len`] is O(1).
+    len: usize,
 }
 
-impl Default for PrefixTree {
+impl<V> Default for PrefixTree<V> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl PrefixTree {
+impl<V> PrefixTree<V> {
     /// Create a new empty Prefix Tree with a ROOT node.
     pub fn new() -> Self {
         Self {
-            root: Box::new(Node::Node {
-                children: HashMap::new(),
-            }),
+            root: Box::new(Node::branch(HashMap::new())),
+            len: 0,
         }
     }
 
@@ -61,21 +270,37 @@ impl PrefixTree {
             .count()
     }
 
-    /// Insert a key-value pair into the tree.
-    pub fn insert(&mut self, key: &str, value: String) {
-        Self::insert_recursive(&mut self.root, key, value);
+    /// Insert a key-value pair into the tree, returning the previous value
+    /// stored at `key` if one existed.
+    pub fn insert(&mut self, key: &str, value: V) -> Option<V> {
+        let old_value = Self::insert_recursive(&mut self.root, key, value);
+        if old_value.is_none() {
+            self.len += 1;
+        }
+        old_value
     }
 
-    fn insert_recursive(node: &mut Node, key: &str, value: String) {
+    fn insert_recursive(node: &mut Node<V>, key: &str, value: V) -> Option<V> {
+        // This node (or one of its descendants) is about to change, so its
+        // cached commitment is no longer valid.
+        node.invalidate_cache();
+
         let key_with_colon = format!("{}:", key);
 
-        if let Node::Node { children } = node {
+        if let Node::Node { children, .. } = node {
             // Case 1: The key is a prefix of an existing child's key.
-            // e.g., inserting "foo" when "foobar" exists.
+            // e.g., inserting "foo" when "foobar" exists, or when a
+            // terminal leaf "foobar:" exists (excluding the case where
+            // `child_key` is just `key`'s own "X:" leaf, which Case 4
+            // already handles as an exact-match overwrite).
+            let key_with_colon_self = format!("{}:", key);
             let mut prefix_child_key: Option<String> = None;
             if !key.is_empty() {
                 for child_key in children.keys() {
-                    if child_key.starts_with(key) && child_key.len() > key.len() {
+                    if child_key.starts_with(key)
+                        && child_key.len() > key.len()
+                        && *child_key != key_with_colon_self
+                    {
                         prefix_child_key = Some(child_key.clone());
                         break;
                     }
@@ -87,32 +312,56 @@ impl PrefixTree {
                 let suffix = &child_key[key.len()..];
 
                 let mut new_node_children = HashMap::new();
-                new_node_children.insert(":".to_string(), Box::new(Node::Leaf { value }));
+                new_node_children.insert(":".to_string(), Box::new(Node::leaf(value)));
                 new_node_children.insert(suffix.to_string(), existing_child);
 
-                children.insert(
-                    key.to_string(),
-                    Box::new(Node::Node {
-                        children: new_node_children,
-                    }),
-                );
-                return;
+                children.insert(key.to_string(), Box::new(Node::branch(new_node_children)));
+                return None;
             }
 
-            // Case 2: An existing child's key is a prefix of the new key.
-            // e.g., inserting "foobar" when "foo" node exists.
+            // Case 2: An existing child's key is a prefix of the new key
+            // (or exactly equal to it, when the child is a branch edge).
+            // e.g., inserting "foobar" when a "foo" branch edge exists,
+            // inserting "foo" when a "foo" branch edge already exists (the
+            // new value belongs in that branch's own ":" slot), or
+            // inserting "foobar" when a terminal leaf "foo:" exists — the
+            // latter must first be split into a branch so "foobar" can be
+            // added alongside it.
             let mut child_prefix_key: Option<String> = None;
             for child_key in children.keys() {
-                if !child_key.ends_with(':') && key.starts_with(child_key) {
+                if child_key.ends_with(':') {
+                    let compare_key = &child_key[..child_key.len() - 1];
+                    if !compare_key.is_empty()
+                        && key.len() > compare_key.len()
+                        && key.starts_with(compare_key)
+                    {
+                        child_prefix_key = Some(child_key.clone());
+                        break;
+                    }
+                } else if !child_key.is_empty() && key.starts_with(child_key.as_str()) {
                     child_prefix_key = Some(child_key.clone());
                     break;
                 }
             }
             if let Some(child_key) = child_prefix_key {
+                if child_key.ends_with(':') {
+                    let existing_leaf = children.remove(&child_key).unwrap();
+                    let edge_len = child_key.len() - 1;
+                    let edge = key[..edge_len].to_string();
+                    let suffix = &key[edge_len..];
+
+                    let mut branch_children = HashMap::new();
+                    branch_children.insert(":".to_string(), existing_leaf);
+                    let mut branch = Node::branch(branch_children);
+                    Self::insert_recursive(&mut branch, suffix, value);
+
+                    children.insert(edge, Box::new(branch));
+                    return None;
+                }
+
                 let child_node = children.get_mut(&child_key).unwrap();
                 let suffix = &key[child_key.len()..];
-                Self::insert_recursive(child_node, suffix, value);
-                return;
+                return Self::insert_recursive(child_node, suffix, value);
             }
 
             // Case 3: Partial prefix match - needs splitting.
@@ -141,7 +390,7 @@ impl PrefixTree {
                         &child_key
                     };
                     if max_common_len < child_compare_key.len() {
-                        let mut existing_child = children.remove(&child_key).unwrap();
+                        let existing_child = children.remove(&child_key).unwrap();
                         let shared_prefix = &key[..max_common_len];
 
                         let new_suffix = &key[max_common_len..];
@@ -149,43 +398,128 @@ impl PrefixTree {
 
                         let mut new_children = HashMap::new();
                         new_children
-                            .insert(format!("{}:", new_suffix), Box::new(Node::Leaf { value }));
+                            .insert(format!("{}:", new_suffix), Box::new(Node::leaf(value)));
                         new_children.insert(existing_suffix.to_string(), existing_child);
 
                         children.insert(
                             shared_prefix.to_string(),
-                            Box::new(Node::Node {
-                                children: new_children,
-                            }),
+                            Box::new(Node::branch(new_children)),
                         );
-                        return;
+                        return None;
                     }
                 }
             }
 
             // Case 4: Exact match or no prefix relationship.
             if let Some(child) = children.get_mut(&key_with_colon) {
-                if let Node::Leaf { value: old_value } = child.as_mut() {
-                    *old_value = value;
-                    return;
+                if let Node::Leaf { value: old_value, hash_cache } = child.as_mut() {
+                    hash_cache.set(None);
+                    return Some(std::mem::replace(old_value, value));
                 }
             }
-            children.insert(key_with_colon, Box::new(Node::Leaf { value }));
+            children.insert(key_with_colon, Box::new(Node::leaf(value)));
+            return None;
+        }
+        None
+    }
+
+    /// Remove `key` from the tree, returning its value if it was present.
+    ///
+    /// If removing the leaf leaves its parent branch with exactly one
+    /// remaining child, that child is merged back into the parent by
+    /// concatenating their edges (a lone `:` value child becomes a `key:`
+    /// leaf) — the
+    /// inverse of the splitting performed by Case 3 of
+    /// [`Self::insert_recursive`] — so the tree stays minimal.
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        let removed = Self::remove_recursive(&mut self.root, key);
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    fn remove_recursive(node: &mut Node<V>, key: &str) -> Option<V> {
+        node.invalidate_cache();
+
+        let children = match node {
+            Node::Node { children, .. } => children,
+            Node::Leaf { .. } => return None,
+        };
+
+        if key.is_empty() {
+            if let Some(child) = children.get(":") {
+                if matches!(&**child, Node::Leaf { .. }) {
+                    if let Node::Leaf { value, .. } = *children.remove(":").unwrap() {
+                        return Some(value);
+                    }
+                }
+            }
+            return None;
+        }
+
+        let key_with_colon = format!("{}:", key);
+        if let Some(child) = children.get(&key_with_colon) {
+            if matches!(&**child, Node::Leaf { .. }) {
+                if let Node::Leaf { value, .. } = *children.remove(&key_with_colon).unwrap() {
+                    return Some(value);
+                }
+            }
+        }
+
+        let matched_edge = children
+            .keys()
+            .find(|child_key| !child_key.ends_with(':') && key.starts_with(child_key.as_str()))
+            .cloned();
+
+        let edge = matched_edge?;
+        let suffix = key[edge.len()..].to_string();
+        let removed = Self::remove_recursive(children.get_mut(&edge).unwrap(), &suffix);
+        if removed.is_some() {
+            Self::collapse_single_child(children, &edge);
+        }
+        removed
+    }
+
+    /// If the child of `children` under `edge` is a branch with exactly one
+    /// remaining child, merge that lone child back into `children` under
+    /// the concatenated edge (a lone `:` value becomes an `edge:` leaf),
+    /// eliminating the now-redundant intermediate branch.
+    fn collapse_single_child(children: &mut HashMap<String, Box<Node<V>>>, edge: &str) {
+        let should_collapse = match children.get(edge).map(|child| &**child) {
+            Some(Node::Node {
+                children: grandchildren,
+                ..
+            }) => grandchildren.len() == 1,
+            _ => false,
+        };
+        if !should_collapse {
+            return;
+        }
+
+        let child = children.remove(edge).unwrap();
+        if let Node::Node {
+            children: mut grandchildren,
+            ..
+        } = *child
+        {
+            let (sub_edge, sub_child) = grandchildren.drain().next().unwrap();
+            children.insert(format!("{}{}", edge, sub_edge), sub_child);
         }
     }
 
     /// Get a value by key from the tree.
-    pub fn get(&self, key: &str) -> Option<String> {
+    pub fn get(&self, key: &str) -> Option<&V> {
         Self::get_recursive(&self.root, key)
     }
 
-    fn get_recursive(node: &Node, key: &str) -> Option<String> {
-        if let Node::Node { children } = node {
+    fn get_recursive<'a>(node: &'a Node<V>, key: &str) -> Option<&'a V> {
+        if let Node::Node { children, .. } = node {
             // Check for a node value first (a ":" leaf)
             if key.is_empty() {
                 if let Some(value_node) = children.get(":") {
-                    if let Node::Leaf { value } = &**value_node {
-                        return Some(value.clone());
+                    if let Node::Leaf { value, .. } = &**value_node {
+                        return Some(value);
                     }
                 }
             }
@@ -193,8 +527,8 @@ impl PrefixTree {
             // Check for direct leaf match.
             let key_with_colon = format!("{}:", key);
             if let Some(leaf_node) = children.get(&key_with_colon) {
-                if let Node::Leaf { value } = &**leaf_node {
-                    return Some(value.clone());
+                if let Node::Leaf { value, .. } = &**leaf_node {
+                    return Some(value);
                 }
             }
 
@@ -209,6 +543,57 @@ impl PrefixTree {
         None
     }
 
+    /// Get a mutable reference to a value by key from the tree.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        Self::get_mut_recursive(&mut self.root, key)
+    }
+
+    fn get_mut_recursive<'a>(node: &'a mut Node<V>, key: &str) -> Option<&'a mut V> {
+        // The caller gets a `&mut V` straight into the tree with no further
+        // hook for us to observe a mutation through it, so — mirroring
+        // insert_recursive/remove_recursive — invalidate eagerly along the
+        // path to the returned value rather than only once we know it
+        // exists: every node visited here is a node whose commitment the
+        // caller could be about to change.
+        node.invalidate_cache();
+
+        let children = match node {
+            Node::Node { children, .. } => children,
+            Node::Leaf { .. } => return None,
+        };
+
+        // Decide, via immutable lookups, which single child this call needs
+        // to reach before taking any `get_mut` — chaining several `get_mut`
+        // calls on the same map conflicts under NLL once the return value
+        // borrows from `node`.
+        if key.is_empty() {
+            let value_node = children.get_mut(":")?;
+            value_node.invalidate_cache();
+            return match value_node.as_mut() {
+                Node::Leaf { value, .. } => Some(value),
+                Node::Node { .. } => None,
+            };
+        }
+
+        let key_with_colon = format!("{}:", key);
+        if children.contains_key(&key_with_colon) {
+            let leaf_node = children.get_mut(&key_with_colon).unwrap();
+            leaf_node.invalidate_cache();
+            return match leaf_node.as_mut() {
+                Node::Leaf { value, .. } => Some(value),
+                Node::Node { .. } => None,
+            };
+        }
+
+        let matched_edge = children
+            .keys()
+            .find(|child_key| !child_key.ends_with(':') && key.starts_with(child_key.as_str()))
+            .cloned()?;
+
+        let suffix = key[matched_edge.len()..].to_string();
+        Self::get_mut_recursive(children.get_mut(&matched_edge).unwrap(), &suffix)
+    }
+
     /// Get all keys in the tree.
     pub fn keys(&self) -> Vec<String> {
         let mut keys = Vec::new();
@@ -216,8 +601,8 @@ impl PrefixTree {
         keys
     }
 
-    fn collect_keys_recursive(node: &Node, prefix: &str, keys: &mut Vec<String>) {
-        if let Node::Node { children } = node {
+    fn collect_keys_recursive(node: &Node<V>, prefix: &str, keys: &mut Vec<String>) {
+        if let Node::Node { children, .. } = node {
             for (child_key, child_node) in children {
                 let new_prefix = format!("{}{}", prefix, child_key);
                 if child_key == ":" {
@@ -233,17 +618,470 @@ impl PrefixTree {
 
     /// Get the number of key-value pairs in the tree.
     pub fn len(&self) -> usize {
-        self.keys().len()
+        self.len
     }
 
     /// Check if the tree is empty.
     pub fn is_empty(&self) -> bool {
-        if let Node::Node { children } = &*self.root {
-            return children.is_empty();
+        self.len == 0
+    }
+
+    /// Iterate over every `(key, value)` pair in the tree. The order is
+    /// not defined: children are visited in `HashMap` iteration order.
+    /// Unlike [`Self::keys`], this does not
+    /// materialize the full key set up front: traversal is driven by an
+    /// explicit stack of `(accumulated_prefix, children_iter)` frames.
+    pub fn iter(&self) -> impl Iterator<Item = (String, &V)> {
+        let children = match &*self.root {
+            Node::Node { children, .. } => children.iter(),
+            Node::Leaf { .. } => unreachable!("the tree root is always a branch node"),
+        };
+        Iter {
+            stack: vec![(String::new(), children)],
+            pending: None,
+        }
+    }
+
+    /// Iterate over every value in the tree, in the same order as [`Self::iter`].
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Iterate over every `(key, value)` pair whose key extends `prefix`,
+    /// restricted to the subtree rooted at `prefix` rather than scanning
+    /// the whole tree.
+    pub fn iter_prefix(&self, prefix: &str) -> impl Iterator<Item = (String, &V)> {
+        match Self::prefix_start(&self.root, prefix, "") {
+            PrefixStart::None => Iter {
+                stack: Vec::new(),
+                pending: None,
+            },
+            PrefixStart::Leaf(key, value) => Iter {
+                stack: Vec::new(),
+                pending: Some((key, value)),
+            },
+            PrefixStart::Node(acc, Node::Node { children, .. }) => Iter {
+                stack: vec![(acc, children.iter())],
+                pending: None,
+            },
+            PrefixStart::Node(_, Node::Leaf { .. }) => Iter {
+                stack: Vec::new(),
+                pending: None,
+            },
+        }
+    }
+
+    /// Locate where [`Self::iter_prefix`] should start iterating: either
+    /// nowhere (`prefix` matches nothing), a single leaf (`prefix` lands
+    /// exactly on a stored key that happens to have no descendants), or the
+    /// subtree node whose entire contents extend `prefix`.
+    fn prefix_start<'a>(node: &'a Node<V>, key: &str, acc: &str) -> PrefixStart<'a, V> {
+        let children = match node {
+            Node::Node { children, .. } => children,
+            Node::Leaf { .. } => return PrefixStart::None,
+        };
+
+        if key.is_empty() {
+            return PrefixStart::Node(acc.to_string(), node);
+        }
+
+        for (child_key, child_node) in children {
+            if let Some(leaf_key) = child_key.strip_suffix(':') {
+                if leaf_key.starts_with(key) {
+                    if let Node::Leaf { value, .. } = &**child_node {
+                        return PrefixStart::Leaf(format!("{}{}", acc, leaf_key), value);
+                    }
+                }
+                continue;
+            }
+            let new_acc = format!("{}{}", acc, child_key);
+            if key.starts_with(child_key.as_str()) {
+                let suffix = &key[child_key.len()..];
+                return Self::prefix_start(child_node, suffix, &new_acc);
+            } else if child_key.starts_with(key) {
+                return PrefixStart::Node(new_acc, child_node);
+            }
+        }
+        PrefixStart::None
+    }
+}
+
+/// Where an [`PrefixTree::iter_prefix`] traversal should begin.
+enum PrefixStart<'a, V> {
+    /// No stored key extends the requested prefix.
+    None,
+    /// The requested prefix matches exactly one stored leaf with no
+    /// descendants of its own.
+    Leaf(String, &'a V),
+    /// Every key under this node (accumulated prefix, node) extends the
+    /// requested prefix.
+    Node(String, &'a Node<V>),
+}
+
+/// One stack frame of an in-progress [`Iter`] traversal: the prefix
+/// accumulated to reach this node, and an iterator over its children.
+type ChildFrame<'a, V> = (String, std::collections::hash_map::Iter<'a, String, Box<Node<V>>>);
+
+/// Lazy, stack-based iterator over the `(key, value)` pairs of a
+/// [`PrefixTree`], returned by [`PrefixTree::iter`], [`PrefixTree::values`]
+/// and [`PrefixTree::iter_prefix`].
+struct Iter<'a, V> {
+    stack: Vec<ChildFrame<'a, V>>,
+    pending: Option<(String, &'a V)>,
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (String, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(item) = self.pending.take() {
+            return Some(item);
         }
-        false
+
+        while let Some((prefix, children)) = self.stack.last_mut() {
+            match children.next() {
+                None => {
+                    self.stack.pop();
+                }
+                Some((edge, child)) => {
+                    let prefix = prefix.clone();
+                    if let Some(leaf_key) = edge.strip_suffix(':') {
+                        if let Node::Leaf { value, .. } = &**child {
+                            return Some((format!("{}{}", prefix, leaf_key), value));
+                        }
+                    } else if let Node::Node { children, .. } = &**child {
+                        let new_prefix = format!("{}{}", prefix, edge);
+                        self.stack.push((new_prefix, children.iter()));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<V: Clone> PrefixTree<V> {
+    /// Find every stored key that is a prefix of `key`, along with its
+    /// value. The stored keys may be shorter than `key` or equal to it.
+    pub fn find_prefixes(&self, key: &str) -> Vec<(String, V)> {
+        let mut results = Vec::new();
+        Self::find_prefixes_recursive(&self.root, key, "", &mut results);
+        results
     }
 
+    fn find_prefixes_recursive(
+        node: &Node<V>,
+        key: &str,
+        acc: &str,
+        results: &mut Vec<(String, V)>,
+    ) {
+        let children = match node {
+            Node::Node { children, .. } => children,
+            Node::Leaf { .. } => return,
+        };
+
+        // This node's own value (a ":" leaf), if any, is always a prefix of
+        // the full query, regardless of how much of `key` remains.
+        if let Some(value_node) = children.get(":") {
+            if let Node::Leaf { value, .. } = &**value_node {
+                results.push((acc.to_string(), value.clone()));
+            }
+        }
+
+        for (child_key, child_node) in children {
+            if let Some(leaf_key) = child_key.strip_suffix(':') {
+                // A direct leaf is a prefix match if its own key is a
+                // prefix of (or equal to) what remains of the query.
+                if !leaf_key.is_empty() && key.starts_with(leaf_key) {
+                    if let Node::Leaf { value, .. } = &**child_node {
+                        results.push((format!("{}{}", acc, leaf_key), value.clone()));
+                    }
+                }
+            } else if key.starts_with(child_key.as_str()) {
+                // Descend into an intermediate edge that is itself a
+                // prefix of the remaining key.
+                let suffix = &key[child_key.len()..];
+                let new_acc = format!("{}{}", acc, child_key);
+                Self::find_prefixes_recursive(child_node, suffix, &new_acc, results);
+            }
+        }
+    }
+
+    /// Find the longest stored key that is a prefix of `key`, along with
+    /// its value.
+    pub fn find_longest_prefix(&self, key: &str) -> Option<(String, V)> {
+        self.find_prefixes(key)
+            .into_iter()
+            .max_by_key(|(stored_key, _)| stored_key.len())
+    }
+
+    /// Find every stored key that extends `prefix`, along with its value.
+    /// The stored key equal to `prefix` itself is included if present.
+    pub fn find_postfixes(&self, prefix: &str) -> Vec<(String, V)> {
+        let mut results = Vec::new();
+        Self::find_postfixes_recursive(&self.root, prefix, "", &mut results);
+        results
+    }
+
+    fn find_postfixes_recursive(
+        node: &Node<V>,
+        key: &str,
+        acc: &str,
+        results: &mut Vec<(String, V)>,
+    ) {
+        let children = match node {
+            Node::Node { children, .. } => children,
+            Node::Leaf { .. } => return,
+        };
+
+        if key.is_empty() {
+            // The whole prefix has been consumed by the path so far: every
+            // key reachable from here extends it.
+            Self::collect_entries_recursive(node, acc, results);
+            return;
+        }
+
+        for (child_key, child_node) in children {
+            if let Some(leaf_key) = child_key.strip_suffix(':') {
+                // A direct leaf extends the prefix if its own key starts
+                // with whatever remains of it (this also covers the case
+                // where the leaf's key equals the prefix exactly).
+                if leaf_key.starts_with(key) {
+                    if let Node::Leaf { value, .. } = &**child_node {
+                        results.push((format!("{}{}", acc, leaf_key), value.clone()));
+                    }
+                }
+                continue;
+            }
+            let new_acc = format!("{}{}", acc, child_key);
+            if key.starts_with(child_key.as_str()) {
+                // This edge is fully consumed by the prefix; keep descending.
+                let suffix = &key[child_key.len()..];
+                Self::find_postfixes_recursive(child_node, suffix, &new_acc, results);
+            } else if child_key.starts_with(key) {
+                // The prefix ends partway along this edge, so every key in
+                // this whole subtree already extends it.
+                Self::collect_entries_recursive(child_node, &new_acc, results);
+            }
+        }
+    }
+
+    /// Collect every (key, value) pair reachable from `node`, prefixing
+    /// each key with the path already consumed to reach it. Used by the
+    /// prefix/postfix query family; `keys()` uses the lighter-weight
+    /// `collect_keys_recursive` since it only needs the keys.
+    fn collect_entries_recursive(node: &Node<V>, prefix: &str, results: &mut Vec<(String, V)>) {
+        if let Node::Node { children, .. } = node {
+            for (child_key, child_node) in children {
+                let new_prefix = format!("{}{}", prefix, child_key);
+                if child_key == ":" {
+                    if let Node::Leaf { value, .. } = &**child_node {
+                        results.push((prefix.to_string(), value.clone()));
+                    }
+                } else if child_key.ends_with(':') {
+                    if let Node::Leaf { value, .. } = &**child_node {
+                        let key = new_prefix[..new_prefix.len() - 1].to_string();
+                        results.push((key, value.clone()));
+                    }
+                } else {
+                    Self::collect_entries_recursive(child_node, &new_prefix, results);
+                }
+            }
+        }
+    }
+}
+
+impl<V: AsRef<[u8]>> PrefixTree<V> {
+    /// Compute the root commitment of the tree: a Keccak256 hash that
+    /// changes if and only if any key or value in the tree changes.
+    ///
+    /// Node hashes are cached and invalidated only along the path touched
+    /// by [`PrefixTree::insert`], [`PrefixTree::remove`] and
+    /// [`PrefixTree::get_mut`], so repeated calls are cheap between
+    /// mutations.
+    pub fn root_hash(&self) -> Hash {
+        self.root.commitment()
+    }
+
+    /// Build a Merkle inclusion proof that `key` maps to its stored value,
+    /// or `None` if `key` is not present.
+    ///
+    /// The proof is the list of sibling-hash sets along the root-to-leaf
+    /// path; [`verify_proof`] recomputes each ancestor's hash bottom-up and
+    /// checks the result against a claimed root hash.
+    pub fn prove(&self, key: &str) -> Option<Proof> {
+        let mut steps = Vec::new();
+        Self::prove_recursive(&self.root, key, &mut steps)?;
+        Some(Proof { steps })
+    }
+
+    fn prove_recursive(node: &Node<V>, key: &str, steps: &mut Vec<ProofStep>) -> Option<()> {
+        let children = match node {
+            Node::Node { children, .. } => children,
+            Node::Leaf { .. } => return None,
+        };
+
+        let path_edge = if key.is_empty() && children.contains_key(":") {
+            ":".to_string()
+        } else if children.contains_key(&format!("{}:", key)) {
+            format!("{}:", key)
+        } else {
+            children
+                .keys()
+                .find(|child_key| !child_key.ends_with(':') && key.starts_with(child_key.as_str()))
+                .cloned()?
+        };
+
+        let siblings = children
+            .iter()
+            .filter(|(edge, _)| edge.as_str() != path_edge)
+            .map(|(edge, child)| (edge.clone(), child.commitment()))
+            .collect();
+        steps.push(ProofStep {
+            siblings,
+            path_edge: path_edge.clone(),
+        });
+
+        if path_edge == ":" || path_edge.ends_with(':') {
+            return Some(());
+        }
+
+        let suffix = &key[path_edge.len()..];
+        Self::prove_recursive(&children[&path_edge], suffix, steps)
+    }
+
+    /// Encode the tree into a compact, canonical binary format: two trees
+    /// with identical contents always encode to identical bytes,
+    /// regardless of insertion order or `HashMap` iteration order.
+    ///
+    /// Depth-first, per node: a tag byte (`LEAF_TAG` or `NODE_TAG`); a leaf
+    /// is then a varint-length-prefixed value; a branch is then a varint
+    /// child count followed by each `(len-prefixed edge, subtree)` in
+    /// lexicographically-sorted edge order.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        Self::encode_node(&self.root, &mut buf);
+        buf
+    }
+
+    fn encode_node(node: &Node<V>, buf: &mut Vec<u8>) {
+        match node {
+            Node::Leaf { value, .. } => {
+                buf.push(LEAF_TAG);
+                encode_len_prefixed(buf, value.as_ref());
+            }
+            Node::Node { children, .. } => {
+                buf.push(NODE_TAG);
+                let mut entries: Vec<_> = children.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                encode_varint(buf, entries.len() as u64);
+                for (edge, child) in entries {
+                    encode_len_prefixed(buf, edge.as_bytes());
+                    Self::encode_node(child, buf);
+                }
+            }
+        }
+    }
+}
+
+impl<V> PrefixTree<V>
+where
+    V: TryFrom<Vec<u8>>,
+    V::Error: fmt::Display,
+{
+    /// Decode a tree previously written by [`PrefixTree::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut pos = 0;
+        let root = Self::decode_node(bytes, &mut pos)?;
+        match root {
+            Node::Node { .. } => {
+                let mut keys = Vec::new();
+                Self::collect_keys_recursive(&root, "", &mut keys);
+                Ok(Self {
+                    root: Box::new(root),
+                    len: keys.len(),
+                })
+            }
+            Node::Leaf { .. } => Err(DecodeError::InvalidTag(LEAF_TAG)),
+        }
+    }
+
+    fn decode_node(bytes: &[u8], pos: &mut usize) -> Result<Node<V>, DecodeError> {
+        let tag = *bytes.get(*pos).ok_or(DecodeError::UnexpectedEof)?;
+        *pos += 1;
+        match tag {
+            LEAF_TAG => {
+                let raw = decode_len_prefixed(bytes, pos)?.to_vec();
+                let value = V::try_from(raw).map_err(|e| DecodeError::InvalidValue(e.to_string()))?;
+                Ok(Node::leaf(value))
+            }
+            NODE_TAG => {
+                let count = decode_varint(bytes, pos)?;
+                let mut children = HashMap::with_capacity(count as usize);
+                for _ in 0..count {
+                    let edge = String::from_utf8(decode_len_prefixed(bytes, pos)?.to_vec())
+                        .map_err(|_| DecodeError::InvalidEdge)?;
+                    let child = Self::decode_node(bytes, pos)?;
+                    children.insert(edge, Box::new(child));
+                }
+                Ok(Node::branch(children))
+            }
+            other => Err(DecodeError::InvalidTag(other)),
+        }
+    }
+}
+
+/// Verify that `key` maps to `value` under the commitment scheme used by
+/// [`PrefixTree::root_hash`] and [`PrefixTree::prove`], without needing
+/// access to the tree itself. `value` is the same byte representation
+/// (`V::as_ref`) that was hashed into the tree.
+pub fn verify_proof(root: Hash, key: &str, value: &[u8], proof: &Proof) -> bool {
+    if proof.steps.is_empty() {
+        return false;
+    }
+
+    // Walk the claimed path forward and check it actually spells out `key`.
+    let mut remaining = key;
+    let last = proof.steps.len() - 1;
+    for (i, step) in proof.steps.iter().enumerate() {
+        if step.path_edge == ":" {
+            if i != last || !remaining.is_empty() {
+                return false;
+            }
+        } else if let Some(edge) = step.path_edge.strip_suffix(':') {
+            if i != last || remaining != edge {
+                return false;
+            }
+        } else {
+            if i == last || !remaining.starts_with(step.path_edge.as_str()) {
+                return false;
+            }
+            remaining = &remaining[step.path_edge.len()..];
+        }
+    }
+
+    // Recompute each ancestor's hash bottom-up from the claimed leaf value.
+    let mut buf = Vec::with_capacity(1 + value.len());
+    buf.push(LEAF_TAG);
+    buf.extend_from_slice(value);
+    let mut current_hash = hash_bytes(&buf);
+
+    for step in proof.steps.iter().rev() {
+        let mut entries = step.siblings.clone();
+        entries.push((step.path_edge.clone(), current_hash));
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut buf = vec![NODE_TAG];
+        for (edge, hash) in &entries {
+            encode_edge(&mut buf, edge, *hash);
+        }
+        current_hash = hash_bytes(&buf);
+    }
+
+    current_hash == root
+}
+
+impl<V: fmt::Display> PrefixTree<V> {
     /// Display the tree structure graphically in the console.
     pub fn display_tree(&self) {
         println!("Prefix Tree Structure:");
@@ -253,8 +1091,8 @@ impl PrefixTree {
         println!("=====================");
     }
 
-    fn display_node_recursive(node: &Node, prefix: &str) {
-        if let Node::Node { children } = node {
+    fn display_node_recursive(node: &Node<V>, prefix: &str) {
+        if let Node::Node { children, .. } = node {
             let mut entries: Vec<_> = children.iter().collect();
             entries.sort_by(|a, b| a.0.cmp(b.0));
 
@@ -266,7 +1104,7 @@ impl PrefixTree {
                 };
 
                 match &***child {
-                    Node::Leaf { value } => {
+                    Node::Leaf { value, .. } => {
                         let leaf_key = &key[..key.len() - 1]; // Remove the ":"
                         println!(
                             "{}{} LEAF -> key=\"{}\", value=\"{}\"",
@@ -290,7 +1128,7 @@ mod tests {
 
     #[test]
     fn test_empty_tree() {
-        let tree = PrefixTree::new();
+        let tree: PrefixTree<String> = PrefixTree::new();
         assert!(tree.is_empty());
         assert_eq!(tree.len(), 0);
     }
@@ -301,7 +1139,7 @@ mod tests {
         tree.insert("bar", "val_bar".to_string());
         assert!(!tree.is_empty());
         assert_eq!(tree.len(), 1);
-        assert_eq!(tree.get("bar"), Some("val_bar".to_string()));
+        assert_eq!(tree.get("bar").map(String::as_str), Some("val_bar"));
     }
 
     #[test]
@@ -335,10 +1173,10 @@ mod tests {
         tree.display_tree();
 
         // Verify all values
-        assert_eq!(tree.get("bar"), Some("val_bar".to_string()));
-        assert_eq!(tree.get("foobar"), Some("val_foobar".to_string()));
-        assert_eq!(tree.get("foofoo"), Some("val_foofoo".to_string()));
-        assert_eq!(tree.get("foo"), Some("val_foo".to_string()));
+        assert_eq!(tree.get("bar").map(String::as_str), Some("val_bar"));
+        assert_eq!(tree.get("foobar").map(String::as_str), Some("val_foobar"));
+        assert_eq!(tree.get("foofoo").map(String::as_str), Some("val_foofoo"));
+        assert_eq!(tree.get("foo").map(String::as_str), Some("val_foo"));
         assert_eq!(tree.len(), 4);
 
         println!("\n=== All tests passed! ===");
@@ -358,4 +1196,386 @@ mod tests {
         assert_eq!(keys.len(), 4);
         assert_eq!(keys, vec!["bar", "foo", "foobar", "foofoo"]);
     }
+
+    #[test]
+    fn test_get_mut() {
+        let mut tree = PrefixTree::new();
+        tree.insert("foo", "val_foo".to_string());
+        tree.insert("foobar", "val_foobar".to_string());
+
+        *tree.get_mut("foo").unwrap() = "updated".to_string();
+
+        assert_eq!(tree.get("foo").map(String::as_str), Some("updated"));
+        assert_eq!(tree.get("foobar").map(String::as_str), Some("val_foobar"));
+        assert!(tree.get_mut("missing").is_none());
+    }
+
+    #[test]
+    fn test_get_mut_invalidates_root_hash() {
+        let mut tree = PrefixTree::new();
+        tree.insert("foo", "val_foo".to_string());
+        let before = tree.root_hash();
+
+        *tree.get_mut("foo").unwrap() = "changed".to_string();
+        let after = tree.root_hash();
+
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn test_find_prefixes() {
+        let mut tree = PrefixTree::new();
+        tree.insert("bar", "val_bar".to_string());
+        tree.insert("foobar", "val_foobar".to_string());
+        tree.insert("foofoo", "val_foofoo".to_string());
+        tree.insert("foo", "val_foo".to_string());
+
+        let mut matches = tree.find_prefixes("foobarbaz");
+        matches.sort();
+        assert_eq!(
+            matches,
+            vec![
+                ("foo".to_string(), "val_foo".to_string()),
+                ("foobar".to_string(), "val_foobar".to_string()),
+            ]
+        );
+
+        assert!(tree.find_prefixes("baz").is_empty());
+    }
+
+    #[test]
+    fn test_find_longest_prefix() {
+        let mut tree = PrefixTree::new();
+        tree.insert("foo", "val_foo".to_string());
+        tree.insert("foobar", "val_foobar".to_string());
+
+        assert_eq!(
+            tree.find_longest_prefix("foobarbaz"),
+            Some(("foobar".to_string(), "val_foobar".to_string()))
+        );
+        assert_eq!(tree.find_longest_prefix("baz"), None);
+    }
+
+    #[test]
+    fn test_find_postfixes() {
+        let mut tree = PrefixTree::new();
+        tree.insert("bar", "val_bar".to_string());
+        tree.insert("foobar", "val_foobar".to_string());
+        tree.insert("foofoo", "val_foofoo".to_string());
+        tree.insert("foo", "val_foo".to_string());
+
+        let mut matches = tree.find_postfixes("foo");
+        matches.sort();
+        assert_eq!(
+            matches,
+            vec![
+                ("foo".to_string(), "val_foo".to_string()),
+                ("foobar".to_string(), "val_foobar".to_string()),
+                ("foofoo".to_string(), "val_foofoo".to_string()),
+            ]
+        );
+
+        // A prefix that ends partway through a shared edge still finds
+        // every key under that subtree.
+        let mut partial = tree.find_postfixes("fo");
+        partial.sort();
+        assert_eq!(partial, matches);
+
+        assert!(tree.find_postfixes("foobarbaz").is_empty());
+    }
+
+    #[test]
+    fn test_root_hash_is_deterministic_and_content_sensitive() {
+        let mut a = PrefixTree::new();
+        a.insert("bar", "val_bar".to_string());
+        a.insert("foobar", "val_foobar".to_string());
+
+        let mut b = PrefixTree::new();
+        b.insert("foobar", "val_foobar".to_string());
+        b.insert("bar", "val_bar".to_string());
+
+        // Insertion order must not affect the commitment.
+        assert_eq!(a.root_hash(), b.root_hash());
+
+        b.insert("bar", "changed".to_string());
+        assert_ne!(a.root_hash(), b.root_hash());
+    }
+
+    #[test]
+    fn test_root_hash_is_insertion_order_independent_with_nested_prefixes() {
+        // "ba", "baa" and "bab" share a nested chain of common prefixes, so
+        // the tree's internal shape (not just its key/value contents) must
+        // be independent of insertion order for the root hash to be a pure
+        // function of content.
+        let keys = ["aab", "ba", "baa", "baab", "bab", "bb"];
+
+        let mut forward = PrefixTree::new();
+        for key in keys {
+            forward.insert(key, format!("val_{}", key));
+        }
+
+        let mut reversed = PrefixTree::new();
+        for key in keys.iter().rev() {
+            reversed.insert(key, format!("val_{}", key));
+        }
+
+        assert_eq!(forward.root_hash(), reversed.root_hash());
+    }
+
+    #[test]
+    fn test_root_hash_cache_is_invalidated_on_insert() {
+        let mut tree = PrefixTree::new();
+        tree.insert("foo", "val_foo".to_string());
+        let before = tree.root_hash();
+
+        tree.insert("foobar", "val_foobar".to_string());
+        let after = tree.root_hash();
+
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn test_prove_and_verify_proof_roundtrip() {
+        let mut tree = PrefixTree::new();
+        tree.insert("bar", "val_bar".to_string());
+        tree.insert("foobar", "val_foobar".to_string());
+        tree.insert("foofoo", "val_foofoo".to_string());
+        tree.insert("foo", "val_foo".to_string());
+
+        let root = tree.root_hash();
+
+        for (key, value) in [
+            ("bar", "val_bar"),
+            ("foobar", "val_foobar"),
+            ("foofoo", "val_foofoo"),
+            ("foo", "val_foo"),
+        ] {
+            let proof = tree.prove(key).expect("key should be present");
+            assert!(verify_proof(root, key, value.as_bytes(), &proof));
+        }
+    }
+
+    #[test]
+    fn test_verify_proof_rejects_wrong_value_or_key() {
+        let mut tree = PrefixTree::new();
+        tree.insert("bar", "val_bar".to_string());
+        tree.insert("foobar", "val_foobar".to_string());
+
+        let root = tree.root_hash();
+        let proof = tree.prove("foobar").unwrap();
+
+        assert!(!verify_proof(
+            root,
+            "foobar",
+            "wrong_value".as_bytes(),
+            &proof
+        ));
+        assert!(!verify_proof(root, "bar", "val_bar".as_bytes(), &proof));
+
+        let bogus_root = hash_bytes(b"not the root");
+        assert!(!verify_proof(
+            bogus_root,
+            "foobar",
+            "val_foobar".as_bytes(),
+            &proof
+        ));
+    }
+
+    #[test]
+    fn test_prove_missing_key_returns_none() {
+        let mut tree = PrefixTree::new();
+        tree.insert("bar", "val_bar".to_string());
+        assert!(tree.prove("baz").is_none());
+    }
+
+    #[test]
+    fn test_insert_returns_previous_value() {
+        let mut tree = PrefixTree::new();
+        assert_eq!(tree.insert("foo", "val_foo".to_string()), None);
+        assert_eq!(
+            tree.insert("foo", "updated".to_string()),
+            Some("val_foo".to_string())
+        );
+        assert_eq!(tree.get("foo").map(String::as_str), Some("updated"));
+    }
+
+    #[test]
+    fn test_remove_basic() {
+        let mut tree = PrefixTree::new();
+        tree.insert("bar", "val_bar".to_string());
+        tree.insert("foo", "val_foo".to_string());
+
+        assert_eq!(tree.remove("foo"), Some("val_foo".to_string()));
+        assert_eq!(tree.get("foo"), None);
+        assert_eq!(tree.get("bar").map(String::as_str), Some("val_bar"));
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.remove("foo"), None);
+    }
+
+    #[test]
+    fn test_remove_triggers_remerge() {
+        let mut split = PrefixTree::new();
+        split.insert("foobar", "val_foobar".to_string());
+        split.insert("foofoo", "val_foofoo".to_string());
+        assert_eq!(split.remove("foofoo"), Some("val_foofoo".to_string()));
+
+        let mut plain = PrefixTree::new();
+        plain.insert("foobar", "val_foobar".to_string());
+
+        // After removing "foofoo", the "foo" branch should have collapsed
+        // back into a single "foobar" edge, making the tree structurally
+        // identical (and thus hash-identical) to one that never split.
+        assert_eq!(split.root_hash(), plain.root_hash());
+        assert_eq!(split.get("foobar").map(String::as_str), Some("val_foobar"));
+        assert_eq!(split.keys(), plain.keys());
+    }
+
+    #[test]
+    fn test_to_bytes_from_bytes_roundtrip() {
+        let mut tree = PrefixTree::new();
+        tree.insert("bar", "val_bar".to_string());
+        tree.insert("foobar", "val_foobar".to_string());
+        tree.insert("foofoo", "val_foofoo".to_string());
+        tree.insert("foo", "val_foo".to_string());
+
+        let bytes = tree.to_bytes();
+        let decoded: PrefixTree<String> = PrefixTree::from_bytes(&bytes).unwrap();
+
+        let mut keys = decoded.keys();
+        keys.sort();
+        assert_eq!(keys, vec!["bar", "foo", "foobar", "foofoo"]);
+        assert_eq!(decoded.get("foobar").map(String::as_str), Some("val_foobar"));
+        assert_eq!(decoded.root_hash(), tree.root_hash());
+    }
+
+    #[test]
+    fn test_to_bytes_is_canonical() {
+        let mut a = PrefixTree::new();
+        a.insert("bar", "val_bar".to_string());
+        a.insert("foobar", "val_foobar".to_string());
+
+        let mut b = PrefixTree::new();
+        b.insert("foobar", "val_foobar".to_string());
+        b.insert("bar", "val_bar".to_string());
+
+        // Insertion order (and thus HashMap iteration order) must not
+        // affect the encoded bytes.
+        assert_eq!(a.to_bytes(), b.to_bytes());
+    }
+
+    #[test]
+    fn test_to_bytes_is_canonical_with_nested_prefixes() {
+        // Same nested-prefix key set as
+        // test_root_hash_is_insertion_order_independent_with_nested_prefixes:
+        // sorting children within one HashMap is not sufficient for
+        // canonicity if the tree's own shape is insertion-order dependent.
+        let keys = ["aab", "ba", "baa", "baab", "bab", "bb"];
+
+        let mut forward = PrefixTree::new();
+        for key in keys {
+            forward.insert(key, format!("val_{}", key));
+        }
+
+        let mut reversed = PrefixTree::new();
+        for key in keys.iter().rev() {
+            reversed.insert(key, format!("val_{}", key));
+        }
+
+        assert_eq!(forward.to_bytes(), reversed.to_bytes());
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_truncated_input() {
+        let mut tree = PrefixTree::new();
+        tree.insert("foo", "val_foo".to_string());
+        let mut bytes = tree.to_bytes();
+        bytes.truncate(bytes.len() - 1);
+
+        let result: Result<PrefixTree<String>, DecodeError> = PrefixTree::from_bytes(&bytes);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_iter_yields_every_key_value_pair() {
+        let mut tree = PrefixTree::new();
+        tree.insert("bar", "val_bar".to_string());
+        tree.insert("foobar", "val_foobar".to_string());
+        tree.insert("foofoo", "val_foofoo".to_string());
+        tree.insert("foo", "val_foo".to_string());
+
+        let mut entries: Vec<_> = tree
+            .iter()
+            .map(|(k, v)| (k, v.clone()))
+            .collect();
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec![
+                ("bar".to_string(), "val_bar".to_string()),
+                ("foo".to_string(), "val_foo".to_string()),
+                ("foobar".to_string(), "val_foobar".to_string()),
+                ("foofoo".to_string(), "val_foofoo".to_string()),
+            ]
+        );
+
+        let mut values: Vec<_> = tree.values().cloned().collect();
+        values.sort();
+        assert_eq!(
+            values,
+            vec![
+                "val_bar".to_string(),
+                "val_foo".to_string(),
+                "val_foobar".to_string(),
+                "val_foofoo".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_iter_prefix_restricts_to_subtree() {
+        let mut tree = PrefixTree::new();
+        tree.insert("bar", "val_bar".to_string());
+        tree.insert("foobar", "val_foobar".to_string());
+        tree.insert("foofoo", "val_foofoo".to_string());
+        tree.insert("foo", "val_foo".to_string());
+
+        let mut matches: Vec<_> = tree
+            .iter_prefix("foo")
+            .map(|(k, v)| (k, v.clone()))
+            .collect();
+        matches.sort();
+        assert_eq!(
+            matches,
+            vec![
+                ("foo".to_string(), "val_foo".to_string()),
+                ("foobar".to_string(), "val_foobar".to_string()),
+                ("foofoo".to_string(), "val_foofoo".to_string()),
+            ]
+        );
+
+        assert_eq!(tree.iter_prefix("baz").count(), 0);
+        assert_eq!(tree.iter_prefix("bar").count(), 1);
+    }
+
+    #[test]
+    fn test_len_is_maintained_incrementally() {
+        let mut tree = PrefixTree::new();
+        assert_eq!(tree.len(), 0);
+
+        tree.insert("foo", "val_foo".to_string());
+        tree.insert("foobar", "val_foobar".to_string());
+        assert_eq!(tree.len(), 2);
+
+        // Overwriting an existing key must not change the count.
+        tree.insert("foo", "updated".to_string());
+        assert_eq!(tree.len(), 2);
+
+        tree.remove("foo");
+        assert_eq!(tree.len(), 1);
+        assert!(!tree.is_empty());
+
+        tree.remove("foobar");
+        assert_eq!(tree.len(), 0);
+        assert!(tree.is_empty());
+    }
 }